@@ -0,0 +1,161 @@
+//! Generates Rust source for a Concerto concept made up of `LongProperty`
+//! declarations: a `#[derive(Serialize, Deserialize)]` struct with one field
+//! per property, plus a `validate()` method enforcing any `range` bounds.
+//!
+//! Scoped down from "walk a parsed `Model`": `Model` only carries a
+//! `namespace` today, and the grammar has no concept/property rules to
+//! parse a concept body out of CTO source. Driving this end-to-end needs
+//! that grammar work first, so for now callers assemble the concept name
+//! and its properties themselves.
+
+use crate::parser::property::long_property::LongProperty;
+
+pub fn generate_struct(name: &str, properties: &[LongProperty]) -> String {
+    let mut out = String::new();
+    out.push_str("#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\n");
+    out.push_str(&format!("pub struct {} {{\n", name));
+    for property in properties {
+        out.push_str(&generate_field(property));
+    }
+    out.push_str("}\n");
+
+    let bounded: Vec<_> = properties
+        .iter()
+        .filter(|property| property.domain_validator.is_some())
+        .collect();
+    if !bounded.is_empty() {
+        out.push('\n');
+        out.push_str(&format!("impl {} {{\n", name));
+        out.push_str("    pub fn validate(&self) -> Result<(), String> {\n");
+        for property in bounded {
+            out.push_str(&generate_bounds_check(property));
+        }
+        out.push_str("        Ok(())\n");
+        out.push_str("    }\n");
+        out.push_str("}\n");
+    }
+
+    out
+}
+
+fn rust_field_type(property: &LongProperty) -> &'static str {
+    match (property.is_array, property.is_optional) {
+        (true, _) => "Vec<i64>",
+        (false, true) => "Option<i64>",
+        (false, false) => "i64",
+    }
+}
+
+fn generate_field(property: &LongProperty) -> String {
+    let mut out = String::new();
+    if property.default_value.is_some() {
+        out.push_str("    #[serde(default)]\n");
+    }
+    out.push_str(&format!(
+        "    pub {}: {},\n",
+        property.name,
+        rust_field_type(property)
+    ));
+    out
+}
+
+fn generate_bounds_check(property: &LongProperty) -> String {
+    let domain = property
+        .domain_validator
+        .as_ref()
+        .expect("caller only passes properties with a domain_validator");
+
+    // `Option<i64>` and `Vec<i64>` fields are checked through a closure over `v`.
+    let operand = match (property.is_array, property.is_optional) {
+        (false, false) => format!("self.{}", property.name),
+        _ => String::from("v"),
+    };
+    let mut checks = Vec::new();
+    if let Some(lower) = domain.lower {
+        checks.push(format!("{} < {}", operand, lower));
+    }
+    if let Some(upper) = domain.upper {
+        checks.push(format!("{} > {}", operand, upper));
+    }
+    if checks.is_empty() {
+        return String::new();
+    }
+    let condition = match (property.is_array, property.is_optional) {
+        (true, _) => format!("self.{}.iter().any(|&v| {})", property.name, checks.join(" || ")),
+        (false, true) => format!("self.{}.map_or(false, |v| {})", property.name, checks.join(" || ")),
+        (false, false) => checks.join(" || "),
+    };
+
+    format!(
+        "        if {} {{\n            return Err(String::from(\"{} out of range\"));\n        }}\n",
+        condition,
+        property.name
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::generate_struct;
+    use crate::parser::property::long_property::{LongDefaultValue, LongDomainValidator, LongProperty};
+
+    fn long(name: &str) -> LongProperty {
+        LongProperty {
+            class: String::from("LongProperty"),
+            name: String::from(name),
+            is_optional: false,
+            is_array: false,
+            default_value: None,
+            domain_validator: None,
+        }
+    }
+
+    #[test]
+    fn test_generate_plain_field() {
+        let src = generate_struct("Foo", &[long("bar")]);
+        assert_eq!(
+            src,
+            "#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]\npub struct Foo {\n    pub bar: i64,\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_generate_optional_array_and_default() {
+        let optional = LongProperty {
+            is_optional: true,
+            ..long("bar")
+        };
+        let array = LongProperty {
+            is_array: true,
+            ..long("baz")
+        };
+        let defaulted = LongProperty {
+            default_value: Some(LongDefaultValue::Literal(42)),
+            ..long("qux")
+        };
+        let src = generate_struct("Foo", &[optional, array, defaulted]);
+        assert!(src.contains("pub bar: Option<i64>,"));
+        assert!(src.contains("pub baz: Vec<i64>,"));
+        assert!(src.contains("#[serde(default)]\n    pub qux: i64,"));
+    }
+
+    #[test]
+    fn test_generate_validate_for_ranged_property() {
+        let ranged = LongProperty {
+            domain_validator: Some(LongDomainValidator {
+                lower: Some(0),
+                upper: Some(10),
+            }),
+            ..long("bar")
+        };
+        let src = generate_struct("Foo", &[ranged]);
+        assert!(src.contains("impl Foo {"));
+        assert!(src.contains("pub fn validate(&self) -> Result<(), String> {"));
+        assert!(src.contains("if self.bar < 0 || self.bar > 10 {"));
+    }
+
+    #[test]
+    fn test_generate_no_validate_without_bounds() {
+        let src = generate_struct("Foo", &[long("bar")]);
+        assert!(!src.contains("impl Foo"));
+    }
+}
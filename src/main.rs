@@ -1,7 +1,7 @@
-mod parser;
-
 use std::fs;
 
+use concerto_rs::parser;
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let unparsed = fs::read_to_string("samples/one.cto")?;
 
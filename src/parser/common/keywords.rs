@@ -0,0 +1,15 @@
+use nom::bytes::complete::tag;
+
+use crate::parser::CResult;
+
+pub(crate) fn optional(input: &str) -> CResult<&str, &str> {
+    tag("optional")(input)
+}
+
+pub(crate) fn default(input: &str) -> CResult<&str, &str> {
+    tag("default")(input)
+}
+
+pub(crate) fn range(input: &str) -> CResult<&str, &str> {
+    tag("range")(input)
+}
@@ -0,0 +1,2 @@
+pub(crate) mod keywords;
+pub(crate) mod numeric;
@@ -0,0 +1,15 @@
+use nom::{
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize},
+    sequence::pair,
+};
+
+use crate::parser::CResult;
+
+pub(crate) fn integer_value(input: &str) -> CResult<&str, i32> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
+
+pub(crate) fn long_value(input: &str) -> CResult<&str, i64> {
+    map_res(recognize(pair(opt(char('-')), digit1)), str::parse)(input)
+}
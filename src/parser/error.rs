@@ -0,0 +1,110 @@
+use pest::iterators::Pair;
+use std::fmt;
+
+use crate::parser::Rule;
+
+/// A 1-based line/column position within the parsed source, plus the text
+/// of that line so errors can render a caret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pos {
+    pub line: usize,
+    pub col: usize,
+    pub line_text: String,
+}
+
+impl Pos {
+    pub fn of(pair: &Pair<Rule>) -> Self {
+        let start = pair.as_span().start_pos();
+        let (line, col) = start.line_col();
+        let line_text = start.line_of().trim_end_matches(['\r', '\n']).to_string();
+        Self {
+            line,
+            col,
+            line_text,
+        }
+    }
+}
+
+/// A recoverable parse error carrying the source position it occurred at and
+/// the stack of grammar rules being parsed when it was raised.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub pos: Pos,
+    pub context: Vec<String>,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(pos: Pos, message: impl Into<String>) -> Self {
+        Self {
+            pos,
+            context: Vec::new(),
+            message: message.into(),
+        }
+    }
+
+    pub fn from_pair(pair: &Pair<Rule>, message: impl Into<String>) -> Self {
+        Self::new(Pos::of(pair), message)
+    }
+
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context.push(context.into());
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {}, col {}",
+            self.message, self.pos.line, self.pos.col
+        )?;
+        if !self.context.is_empty() {
+            write!(f, " (while parsing {})", self.context.join(" > "))?;
+        }
+        write!(
+            f,
+            "\n{}\n{}^",
+            self.pos.line_text,
+            " ".repeat(self.pos.col.saturating_sub(1))
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::{ParseError, Pos};
+
+    #[test]
+    fn test_display_without_context() {
+        let pos = Pos {
+            line: 3,
+            col: 17,
+            line_text: String::from("  o Long baz default='Hello'"),
+        };
+        let err = ParseError::new(pos, "expected Long default value");
+        assert_eq!(
+            err.to_string(),
+            "expected Long default value at line 3, col 17\n  o Long baz default='Hello'\n                ^"
+        );
+    }
+
+    #[test]
+    fn test_display_with_context() {
+        let pos = Pos {
+            line: 3,
+            col: 17,
+            line_text: String::from("  o Long baz default='Hello'"),
+        };
+        let err = ParseError::new(pos, "found string literal")
+            .with_context("LongDefaultValue")
+            .with_context("LongProperty");
+        assert_eq!(
+            err.to_string(),
+            "found string literal at line 3, col 17 (while parsing LongDefaultValue > LongProperty)\n  o Long baz default='Hello'\n                ^"
+        );
+    }
+}
@@ -1,9 +1,20 @@
+pub(crate) mod common;
+pub mod error;
 pub(crate) mod namespace;
+pub(crate) mod property;
 pub(crate) mod semver;
+pub mod validate;
+pub(crate) mod version_req;
 
+use nom::error::VerboseError;
 use pest::Parser;
 use pest_derive::Parser;
 
+use crate::parser::error::ParseError;
+
+/// The result type shared by every nom-based property parser in this crate.
+pub(crate) type CResult<I, O> = nom::IResult<I, O, VerboseError<I>>;
+
 #[derive(Parser)]
 #[grammar = "parser/concerto.pest"]
 pub struct ConcertoParser;
@@ -16,7 +27,12 @@ pub fn parse<'a>(input: &'a str) -> Result<Model, Box<dyn std::error::Error>> {
                 match part.as_rule() {
                     Rule::Namespace => model.namespace = namespace::namespace(part)?,
                     Rule::EOI => (),
-                    _ => unreachable!(),
+                    rule => {
+                        return Err(Box::new(
+                            ParseError::from_pair(&part, format!("unexpected {:?}", rule))
+                                .with_context("Model"),
+                        ))
+                    }
                 }
             }
         }
@@ -29,3 +45,10 @@ pub fn parse<'a>(input: &'a str) -> Result<Model, Box<dyn std::error::Error>> {
 pub struct Model {
     pub namespace: namespace::Namespace,
 }
+
+impl Model {
+    /// Regenerates the CTO source text.
+    pub fn to_cto(&self) -> String {
+        self.namespace.to_cto()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::parser::error::{ParseError, Pos};
 use crate::parser::semver::SemVer;
 use crate::parser::Rule;
 use pest::iterators::Pair;
@@ -5,25 +6,41 @@ use pest::iterators::Pair;
 pub(crate) fn namespace(pair: Pair<Rule>) -> Result<Namespace, Box<dyn std::error::Error>> {
     let mut ns = Namespace::default();
 
-    match pair.into_inner().next() {
-        Some(qualified_namespace) => match qualified_namespace.into_inner().next() {
-            Some(namespace_declaration) => match namespace_declaration.as_rule() {
-                Rule::VersionedQualifiedNamespace => {
-                    for part in namespace_declaration.into_inner() {
-                        match part.as_rule() {
-                            Rule::QualifiedName => ns.name = part.as_str().to_string(),
-                            Rule::SemVer => ns.version = Some(crate::parser::semver::semver(part)?),
-                            _ => unreachable!(),
-                        }
+    let namespace_pos = Pos::of(&pair);
+    let qualified_namespace = pair.into_inner().next().ok_or_else(|| {
+        Box::new(ParseError::new(namespace_pos, "expected a qualified namespace"))
+            as Box<dyn std::error::Error>
+    })?;
+
+    let qualified_pos = Pos::of(&qualified_namespace);
+    let namespace_declaration = qualified_namespace.into_inner().next().ok_or_else(|| {
+        Box::new(ParseError::new(qualified_pos, "expected a namespace declaration"))
+            as Box<dyn std::error::Error>
+    })?;
+
+    match namespace_declaration.as_rule() {
+        Rule::VersionedQualifiedNamespace => {
+            for part in namespace_declaration.into_inner() {
+                match part.as_rule() {
+                    Rule::QualifiedName => ns.name = part.as_str().to_string(),
+                    Rule::SemVer => ns.version = Some(crate::parser::semver::semver(part)?),
+                    rule => {
+                        return Err(Box::new(
+                            ParseError::from_pair(&part, format!("unexpected {:?}", rule))
+                                .with_context("VersionedQualifiedNamespace"),
+                        ))
                     }
                 }
-                Rule::QualifiedName => ns.name = namespace_declaration.as_str().to_string(),
-                _ => unreachable!(),
-            },
-            _ => unreachable!(),
-        },
-        _ => unreachable!(),
-    };
+            }
+        }
+        Rule::QualifiedName => ns.name = namespace_declaration.as_str().to_string(),
+        rule => {
+            return Err(Box::new(
+                ParseError::from_pair(&namespace_declaration, format!("unexpected {:?}", rule))
+                    .with_context("Namespace"),
+            ))
+        }
+    }
     Ok(ns)
 }
 
@@ -33,6 +50,30 @@ pub struct Namespace {
     pub version: Option<SemVer>,
 }
 
+/// Serializes as `name[@version]`, with the version in canonical
+/// `major.minor.patch[-prerelease][+build]` form (so `@1.2` becomes `@1.2.0`).
+impl serde::Serialize for Namespace {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match &self.version {
+            Some(version) => serializer.serialize_str(&format!("{}@{}", self.name, version)),
+            None => serializer.serialize_str(&self.name),
+        }
+    }
+}
+
+impl Namespace {
+    /// Regenerates the `namespace name[@version]` declaration.
+    pub fn to_cto(&self) -> String {
+        match &self.version {
+            Some(version) => format!("namespace {}@{}", self.name, version),
+            None => format!("namespace {}", self.name),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::parser::{semver::SemVer, ConcertoParser, Rule};
@@ -69,10 +110,22 @@ mod test {
                     major: 1,
                     minor: 0,
                     patch: 42,
-                    prerelease: String::new(),
-                    build: String::new()
+                    prerelease: Vec::new(),
+                    build: Vec::new()
                 })
             }
         )
     }
+
+    #[test]
+    fn test_namespace_serialize_round_trip() {
+        assert_eq!(
+            serde_json::to_string(&parse("namespace com.foo.bar")).unwrap(),
+            "\"com.foo.bar\""
+        );
+        assert_eq!(
+            serde_json::to_string(&parse("namespace com.example.foo@1.0.42")).unwrap(),
+            "\"com.example.foo@1.0.42\""
+        );
+    }
 }
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+/// A registry of named generators that a `LongDefaultValue::Generated` token
+/// resolves against when materializing an instance.
+#[derive(Default)]
+pub struct DefaultProviderRegistry {
+    providers: HashMap<String, Box<dyn Fn() -> i64>>,
+}
+
+impl DefaultProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, name: impl Into<String>, provider: impl Fn() -> i64 + 'static) {
+        self.providers.insert(name.into(), Box::new(provider));
+    }
+
+    /// Invokes the named generator, if one is registered.
+    pub fn resolve(&self, name: &str) -> Option<i64> {
+        self.providers.get(name).map(|provider| provider())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DefaultProviderRegistry;
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    #[test]
+    fn test_register_and_resolve() {
+        let mut providers = DefaultProviderRegistry::new();
+        providers.register("answer", || 42);
+        assert_eq!(providers.resolve("answer"), Some(42));
+        assert_eq!(providers.resolve("missing"), None);
+    }
+
+    #[test]
+    fn test_resolve_invokes_lazily_each_time() {
+        static NEXT: AtomicI64 = AtomicI64::new(0);
+        let mut providers = DefaultProviderRegistry::new();
+        providers.register("nextId", || NEXT.fetch_add(1, Ordering::SeqCst));
+        assert_eq!(providers.resolve("nextId"), Some(0));
+        assert_eq!(providers.resolve("nextId"), Some(1));
+    }
+}
@@ -0,0 +1,84 @@
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{alpha1, alphanumeric1, char, space0, space1},
+    combinator::{map, opt, recognize, success},
+    error::context,
+    multi::many0,
+    sequence::{delimited, pair, preceded, tuple},
+    Parser,
+};
+
+use crate::parser::CResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PrimitiveType {
+    IntegerPropertyType,
+    LongPropertyType,
+}
+
+impl PrimitiveType {
+    fn keyword(self) -> &'static str {
+        match self {
+            PrimitiveType::IntegerPropertyType => "Integer",
+            PrimitiveType::LongPropertyType => "Long",
+        }
+    }
+}
+
+/// Parses `o <Type>[] name` / `o <Type> name`, returning the property name
+/// and whether it was declared as an array.
+pub(crate) fn primitive_property<'a>(
+    property_type: PrimitiveType,
+) -> impl FnMut(&'a str) -> CResult<&'a str, (&'a str, bool)> {
+    move |input: &'a str| {
+        context(
+            "PrimitiveProperty",
+            preceded(
+                tuple((char('o'), space1, tag(property_type.keyword()))),
+                tuple((
+                    map(opt(delimited(char('['), space0, char(']'))), |arr| {
+                        arr.is_some()
+                    }),
+                    preceded(space1, property_name),
+                )),
+            )
+            .map(|(is_array, name)| (name, is_array)),
+        )(input)
+    }
+}
+
+fn property_name(input: &str) -> CResult<&str, &str> {
+    recognize(pair(alpha1, many0(alt((alphanumeric1, tag("_"))))))(input)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Ranged<T> {
+    pub(crate) start: Option<T>,
+    pub(crate) end: Option<T>,
+}
+
+/// Parses a `keyword = [lower, upper]` meta property, e.g. `range = [0, 10]`.
+pub(crate) fn ranged_parser<'a, T: Copy>(
+    input: &'a str,
+    keyword: impl FnMut(&'a str) -> CResult<&'a str, &'a str>,
+    value: impl Fn(&'a str) -> CResult<&'a str, T> + Copy,
+) -> CResult<&'a str, Ranged<T>> {
+    let bound = move |input: &'a str| -> CResult<&'a str, Option<T>> {
+        alt((value.map(Some), success(None))).parse(input)
+    };
+
+    context(
+        "Ranged",
+        preceded(
+            tuple((keyword, space0, char('='), space0, char('['), space0)),
+            tuple((
+                bound,
+                preceded(tuple((space0, char(','), space0)), bound),
+                preceded(space0, char(']')),
+            )),
+        ),
+    )
+    .map(|(start, end, _)| Ranged { start, end })
+    .parse(input)
+}
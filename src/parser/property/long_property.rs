@@ -7,15 +7,19 @@ use nom::{
     sequence::{preceded, tuple},
     Parser,
 };
-use serde_derive::Serialize;
+use serde::Deserialize as _;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
 
 use crate::parser::{
     common::{keywords, numeric::long_value},
+    property::default_provider::DefaultProviderRegistry,
     property::internal::{primitive_property, ranged_parser, PrimitiveType, Ranged},
+    validate::{ErrorCollector, Validator},
     CResult,
 };
 
-#[derive(Debug, PartialEq, Clone, Serialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct LongProperty {
     #[serde(rename = "$class")]
     pub class: String,
@@ -26,12 +30,72 @@ pub struct LongProperty {
     pub is_array: bool,
     #[serde(rename = "default")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub default_value: Option<i64>,
+    pub default_value: Option<LongDefaultValue>,
     #[serde(rename = "range")]
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default, deserialize_with = "deserialize_range")]
     pub domain_validator: Option<LongDomainValidator>,
 }
 
+/// The inverse of [`long_property`].
+impl fmt::Display for LongProperty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "o Long")?;
+        if self.is_array {
+            write!(f, "[]")?;
+        }
+        write!(f, " {}", self.name)?;
+        if self.is_optional {
+            write!(f, " optional")?;
+        }
+        if let Some(default_value) = &self.default_value {
+            write!(f, " default={}", default_value)?;
+        }
+        if let Some(domain_validator) = &self.domain_validator {
+            // `String::from` renders a range with neither bound as "", which
+            // `long_property` cannot read back, so write it as `[,]`.
+            let range = String::from(domain_validator);
+            let range = if range.is_empty() { "[,]" } else { range.as_str() };
+            write!(f, " range={}", range)?;
+        }
+        Ok(())
+    }
+}
+
+impl LongProperty {
+    pub fn to_cto(&self) -> String {
+        self.to_string()
+    }
+
+    /// Resolves this property's default to a concrete value: a `Literal`
+    /// resolves to itself, a `Generated` token is looked up in `providers`.
+    pub fn resolve_default(&self, providers: &DefaultProviderRegistry) -> Option<i64> {
+        match &self.default_value {
+            Some(LongDefaultValue::Literal(value)) => Some(*value),
+            Some(LongDefaultValue::Generated(name)) => providers.resolve(name),
+            None => None,
+        }
+    }
+}
+
+/// A `Long` property's `default=` value: either a constant `Literal`, or a
+/// `Generated` token naming a function in a [`DefaultProviderRegistry`].
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LongDefaultValue {
+    Literal(i64),
+    Generated(String),
+}
+
+impl fmt::Display for LongDefaultValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LongDefaultValue::Literal(value) => write!(f, "{}", value),
+            LongDefaultValue::Generated(name) => write!(f, "{}", name),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct LongDomainValidator {
     pub lower: Option<i64>,
@@ -66,8 +130,52 @@ impl From<Ranged<i64>> for LongDomainValidator {
         }
     }
 }
+
+/// Parses the `range` string form written by `String::from(&LongDomainValidator)`
+/// back into a [`LongDomainValidator`].
+fn parse_domain_validator(raw: &str) -> Result<LongDomainValidator, String> {
+    // A range with neither bound is written as "".
+    if raw.trim().is_empty() {
+        return Ok(LongDomainValidator {
+            lower: None,
+            upper: None,
+        });
+    }
+    let inner = raw
+        .trim()
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .ok_or_else(|| format!("invalid range literal: {}", raw))?;
+
+    let mut bounds = inner.splitn(2, ',');
+    let lower = bounds.next().unwrap_or("").trim();
+    let upper = bounds.next().unwrap_or("").trim();
+
+    let parse_bound = |bound: &str| -> Result<Option<i64>, String> {
+        if bound.is_empty() {
+            Ok(None)
+        } else {
+            bound.parse().map(Some).map_err(|e| format!("{}", e))
+        }
+    };
+
+    Ok(LongDomainValidator {
+        lower: parse_bound(lower)?,
+        upper: parse_bound(upper)?,
+    })
+}
+
+fn deserialize_range<'de, D>(deserializer: D) -> Result<Option<LongDomainValidator>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    parse_domain_validator(&raw)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
 enum LongMetaProperty {
-    Default(i64),
+    Default(LongDefaultValue),
     Domain(LongDomainValidator),
     Optional,
 }
@@ -123,14 +231,24 @@ pub fn long_property<'a>(input: &'a str) -> CResult<&'a str, LongProperty> {
     )(input)
 }
 
-pub fn long_default_value<'a>(input: &'a str) -> CResult<&'a str, i64> {
-    into(context(
+pub fn long_default_value<'a>(input: &'a str) -> CResult<&'a str, LongDefaultValue> {
+    let literal = long_value.map(LongDefaultValue::Literal);
+    let generated = generated_default_value.map(LongDefaultValue::Generated);
+
+    context(
         "LongDefaultValue",
         preceded(
             tuple((keywords::default, space0, char('='), space0)),
-            long_value,
+            alt((literal, generated)),
         ),
-    ))(input)
+    )(input)
+}
+
+/// A symbolic default, e.g. `default=nextId`, naming a function in a
+/// [`DefaultProviderRegistry`].
+fn generated_default_value<'a>(input: &'a str) -> CResult<&'a str, String> {
+    use nom::character::complete::alpha1;
+    into(context("GeneratedDefaultValue", alpha1))(input)
 }
 
 pub fn long_domain_validator<'a>(input: &'a str) -> CResult<&'a str, LongDomainValidator> {
@@ -140,6 +258,84 @@ pub fn long_domain_validator<'a>(input: &'a str) -> CResult<&'a str, LongDomainV
     }
 }
 
+impl Validator for LongProperty {
+    fn validate(&self, value: &serde_json::Value, errs: &ErrorCollector) -> serde_json::Value {
+        if value.is_null() {
+            if !self.is_optional {
+                errs.push(self.name.clone(), "missing required field");
+                return serde_json::Value::Null;
+            }
+            return match self.default() {
+                // A scalar default on an array property fills in a single-element
+                // array, since the field itself is still typed as an array.
+                Some(default) if self.is_array => serde_json::Value::Array(vec![default]),
+                Some(default) => default,
+                None => serde_json::Value::Null,
+            };
+        }
+
+        if self.is_array {
+            return match value.as_array() {
+                Some(items) => serde_json::Value::Array(
+                    items
+                        .iter()
+                        .enumerate()
+                        .map(|(i, item)| {
+                            self.validate_element(item, &format!("{}[{}]", self.name, i), errs)
+                        })
+                        .collect(),
+                ),
+                None => {
+                    errs.push(self.name.clone(), "expected an array of Long");
+                    serde_json::Value::Null
+                }
+            };
+        }
+
+        self.validate_element(value, &self.name, errs)
+    }
+
+    // Only a `Literal` default can be materialized without a `DefaultProviderRegistry`.
+    fn default(&self) -> Option<serde_json::Value> {
+        match &self.default_value {
+            Some(LongDefaultValue::Literal(value)) => Some(serde_json::Value::from(*value)),
+            _ => None,
+        }
+    }
+}
+
+impl LongProperty {
+    fn validate_element(
+        &self,
+        value: &serde_json::Value,
+        path: &str,
+        errs: &ErrorCollector,
+    ) -> serde_json::Value {
+        let n = match value.as_i64() {
+            Some(n) => n,
+            None => {
+                errs.push(path.to_string(), "expected a Long");
+                return serde_json::Value::Null;
+            }
+        };
+
+        if let Some(domain) = &self.domain_validator {
+            if let Some(lower) = domain.lower {
+                if n < lower {
+                    errs.push(path.to_string(), format!("{} is below lower bound {}", n, lower));
+                }
+            }
+            if let Some(upper) = domain.upper {
+                if n > upper {
+                    errs.push(path.to_string(), format!("{} is above upper bound {}", n, upper));
+                }
+            }
+        }
+
+        serde_json::Value::from(n)
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -167,7 +363,7 @@ mod test {
                 super::LongProperty {
                     class: String::from("LongProperty"),
                     name: String::from("baz"),
-                    default_value: Some(42),
+                    default_value: Some(super::LongDefaultValue::Literal(42)),
                     domain_validator: None,
                     is_optional: false,
                     is_array: false,
@@ -183,7 +379,7 @@ mod test {
                 super::LongProperty {
                     class: String::from("LongProperty"),
                     name: String::from("baz"),
-                    default_value: Some(42),
+                    default_value: Some(super::LongDefaultValue::Literal(42)),
                     domain_validator: None,
                     is_optional: true,
                     is_array: false,
@@ -253,7 +449,7 @@ mod test {
                 super::LongProperty {
                     class: String::from("LongProperty"),
                     name: String::from("baz"),
-                    default_value: Some(-42),
+                    default_value: Some(super::LongDefaultValue::Literal(-42)),
                     domain_validator: Some(super::LongDomainValidator {
                         lower: None,
                         upper: Some(100)
@@ -288,7 +484,7 @@ mod test {
                 super::LongProperty {
                     class: String::from("LongProperty"),
                     name: String::from("baz"),
-                    default_value: Some(42),
+                    default_value: Some(super::LongDefaultValue::Literal(42)),
                     domain_validator: Some(super::LongDomainValidator {
                         lower: None,
                         upper: Some(100)
@@ -300,4 +496,201 @@ mod test {
             "Should parse long with both default and range in a different order"
         );
     }
+
+    #[test]
+    fn test_validate() {
+        use super::super::internal::Ranged;
+        use crate::parser::validate::{ErrorCollector, Validator};
+        use serde_json::json;
+
+        let required = super::LongProperty {
+            class: String::from("LongProperty"),
+            name: String::from("baz"),
+            default_value: None,
+            domain_validator: None,
+            is_optional: false,
+            is_array: false,
+        };
+        let errs = ErrorCollector::new();
+        assert_eq!(required.validate(&json!(42), &errs), json!(42));
+        assert!(errs.is_empty());
+
+        let errs = ErrorCollector::new();
+        assert_eq!(required.validate(&json!(null), &errs), json!(null));
+        assert_eq!(errs.into_errors().len(), 1, "missing required field is an error");
+
+        let errs = ErrorCollector::new();
+        assert_eq!(required.validate(&json!("not a long"), &errs), json!(null));
+        assert_eq!(errs.into_errors().len(), 1, "wrong JSON type is an error");
+
+        let optional_with_default = super::LongProperty {
+            default_value: Some(super::LongDefaultValue::Literal(7)),
+            is_optional: true,
+            ..required.clone()
+        };
+        let errs = ErrorCollector::new();
+        assert_eq!(
+            optional_with_default.validate(&json!(null), &errs),
+            json!(7)
+        );
+        assert!(errs.is_empty());
+
+        let ranged = super::LongProperty {
+            domain_validator: Some(
+                super::LongDomainValidator::from(Ranged { start: Some(0), end: Some(10) }),
+            ),
+            ..required.clone()
+        };
+        let errs = ErrorCollector::new();
+        assert_eq!(ranged.validate(&json!(11), &errs), json!(11));
+        assert_eq!(errs.into_errors().len(), 1, "out of range value is an error");
+
+        let array = super::LongProperty {
+            is_array: true,
+            domain_validator: Some(
+                super::LongDomainValidator::from(Ranged { start: Some(0), end: Some(10) }),
+            ),
+            ..required.clone()
+        };
+        let errs = ErrorCollector::new();
+        assert_eq!(
+            array.validate(&json!([1, 2, 11]), &errs),
+            json!([1, 2, 11])
+        );
+        assert_eq!(
+            errs.into_errors().len(),
+            1,
+            "only the out-of-range element should be reported"
+        );
+
+        let array_with_default = super::LongProperty {
+            is_array: true,
+            is_optional: true,
+            default_value: Some(super::LongDefaultValue::Literal(7)),
+            ..required.clone()
+        };
+        let errs = ErrorCollector::new();
+        assert_eq!(
+            array_with_default.validate(&json!(null), &errs),
+            json!([7]),
+            "a scalar default on an array property fills in a single-element array"
+        );
+    }
+
+    #[test]
+    fn test_to_cto() {
+        let prop = super::LongProperty {
+            class: String::from("LongProperty"),
+            name: String::from("baz"),
+            default_value: Some(super::LongDefaultValue::Literal(42)),
+            domain_validator: Some(super::LongDomainValidator {
+                lower: Some(0),
+                upper: Some(10),
+            }),
+            is_optional: false,
+            is_array: false,
+        };
+        assert_eq!(prop.to_cto(), "o Long baz default=42 range=[0, 10]");
+
+        let array_optional = super::LongProperty {
+            is_optional: true,
+            is_array: true,
+            domain_validator: None,
+            default_value: None,
+            ..prop.clone()
+        };
+        assert_eq!(array_optional.to_cto(), "o Long[] baz optional");
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let prop = super::LongProperty {
+            class: String::from("LongProperty"),
+            name: String::from("baz"),
+            default_value: Some(super::LongDefaultValue::Literal(42)),
+            domain_validator: Some(super::LongDomainValidator {
+                lower: Some(0),
+                upper: Some(10),
+            }),
+            is_optional: false,
+            is_array: false,
+        };
+        let json = serde_json::to_string(&prop).unwrap();
+        assert_eq!(serde_json::from_str::<super::LongProperty>(&json).unwrap(), prop);
+
+        let no_range = super::LongProperty {
+            default_value: None,
+            domain_validator: None,
+            ..prop
+        };
+        let json = serde_json::to_string(&no_range).unwrap();
+        assert_eq!(
+            serde_json::from_str::<super::LongProperty>(&json).unwrap(),
+            no_range
+        );
+    }
+
+    #[test]
+    fn test_generated_default_value() {
+        assert_eq!(
+            super::long_property("o Long baz default=nextId"),
+            Ok((
+                "",
+                super::LongProperty {
+                    class: String::from("LongProperty"),
+                    name: String::from("baz"),
+                    default_value: Some(super::LongDefaultValue::Generated(String::from(
+                        "nextId"
+                    ))),
+                    domain_validator: None,
+                    is_optional: false,
+                    is_array: false,
+                }
+            )),
+            "Should parse a symbolic generated default"
+        );
+    }
+
+    #[test]
+    fn test_resolve_default() {
+        use crate::parser::property::default_provider::DefaultProviderRegistry;
+
+        let mut providers = DefaultProviderRegistry::new();
+        providers.register("nextId", || 7);
+
+        let literal = super::LongProperty {
+            class: String::from("LongProperty"),
+            name: String::from("baz"),
+            default_value: Some(super::LongDefaultValue::Literal(42)),
+            domain_validator: None,
+            is_optional: false,
+            is_array: false,
+        };
+        assert_eq!(literal.resolve_default(&providers), Some(42));
+
+        let generated = super::LongProperty {
+            default_value: Some(super::LongDefaultValue::Generated(String::from("nextId"))),
+            ..literal.clone()
+        };
+        assert_eq!(generated.resolve_default(&providers), Some(7));
+
+        let unregistered = super::LongProperty {
+            default_value: Some(super::LongDefaultValue::Generated(String::from("missing"))),
+            ..literal
+        };
+        assert_eq!(unregistered.resolve_default(&providers), None);
+    }
+
+    #[test]
+    fn test_to_cto_generated_default() {
+        let prop = super::LongProperty {
+            class: String::from("LongProperty"),
+            name: String::from("baz"),
+            default_value: Some(super::LongDefaultValue::Generated(String::from("nextId"))),
+            domain_validator: None,
+            is_optional: false,
+            is_array: false,
+        };
+        assert_eq!(prop.to_cto(), "o Long baz default=nextId");
+    }
 }
@@ -0,0 +1,4 @@
+pub(crate) mod default_provider;
+pub(crate) mod integer_property;
+pub(crate) mod internal;
+pub(crate) mod long_property;
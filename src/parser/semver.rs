@@ -1,74 +1,226 @@
+use crate::parser::error::{ParseError, Pos};
 use crate::parser::Rule;
 use pest::iterators::Pair;
+use std::cmp::Ordering;
+use std::fmt;
 
 pub(crate) fn semver(pair: Pair<Rule>) -> Result<SemVer, Box<dyn std::error::Error>> {
     let mut semver = SemVer::default();
 
     for part in pair.into_inner() {
         match part.as_rule() {
-            Rule::Version => match part.into_inner().next() {
-                None => (),
-                Some(version) => match version.as_rule() {
-                    Rule::MajorMinorPatchVersion => {
-                        let ver: Vec<_> = version
-                            .into_inner()
-                            .map(|r| {
-                                let version_part: u32 = r.as_str().parse().unwrap_or(0);
-                                version_part
-                            })
-                            .collect();
-                        // ver will definitely have 3 members, since matching MajorMinorPatchVersion
-                        semver.major = ver[0];
-                        semver.minor = ver[1];
-                        semver.patch = ver[2];
-                    }
-                    Rule::MajorMinorVersion => {
-                        let ver: Vec<_> = version
-                            .into_inner()
-                            .map(|r| {
-                                let version_part: u32 = r.as_str().parse().unwrap_or(0);
-                                version_part
-                            })
-                            .collect();
-                        // ver will definitely have 2 members, since matching MajorMinorVersion
-                        semver.major = ver[0];
-                        semver.minor = ver[1];
-                    }
-                    Rule::MajorVersion => {
-                        let ver: Vec<_> = version
-                            .into_inner()
-                            .map(|r| {
-                                let version_part: u32 = r.as_str().parse().unwrap_or(0);
-                                version_part
-                            })
-                            .collect();
-                        // ver will definitely have 1 member, since matching MajorVersion
-                        semver.major = ver[0];
-                    }
-                    _ => unreachable!(),
-                },
-            },
+            Rule::Version => {
+                let version_pos = Pos::of(&part);
+                match part.into_inner().next() {
+                    None => (),
+                    Some(version) => match version.as_rule() {
+                        Rule::MajorMinorPatchVersion => {
+                            let ver: Vec<_> = version
+                                .into_inner()
+                                .map(|r| {
+                                    let version_part: u32 = r.as_str().parse().unwrap_or(0);
+                                    version_part
+                                })
+                                .collect();
+                            // ver will definitely have 3 members, since matching MajorMinorPatchVersion
+                            semver.major = ver[0];
+                            semver.minor = ver[1];
+                            semver.patch = ver[2];
+                        }
+                        Rule::MajorMinorVersion => {
+                            let ver: Vec<_> = version
+                                .into_inner()
+                                .map(|r| {
+                                    let version_part: u32 = r.as_str().parse().unwrap_or(0);
+                                    version_part
+                                })
+                                .collect();
+                            // ver will definitely have 2 members, since matching MajorMinorVersion
+                            semver.major = ver[0];
+                            semver.minor = ver[1];
+                        }
+                        Rule::MajorVersion => {
+                            let ver: Vec<_> = version
+                                .into_inner()
+                                .map(|r| {
+                                    let version_part: u32 = r.as_str().parse().unwrap_or(0);
+                                    version_part
+                                })
+                                .collect();
+                            // ver will definitely have 1 member, since matching MajorVersion
+                            semver.major = ver[0];
+                        }
+                        rule => {
+                            return Err(Box::new(
+                                ParseError::new(version_pos, format!("unexpected {:?}", rule))
+                                    .with_context("Version"),
+                            ))
+                        }
+                    },
+                }
+            }
             Rule::Prerelease => match part.into_inner().next() {
                 None => (),
-                Some(prerelease) => semver.prerelease = prerelease.as_str().to_string(),
+                Some(prerelease) => semver.prerelease = parse_identifiers(prerelease.as_str()),
             },
             Rule::Build => match part.into_inner().next() {
                 None => (),
-                Some(build) => semver.build = build.as_str().to_string(),
+                Some(build) => semver.build = parse_identifiers(build.as_str()),
             },
-            _ => unreachable!(),
+            rule => {
+                return Err(Box::new(
+                    ParseError::from_pair(&part, format!("unexpected {:?}", rule))
+                        .with_context("SemVer"),
+                ))
+            }
         }
     }
     Ok(semver)
 }
 
-#[derive(Default, Debug, PartialEq)]
+#[derive(Default, Debug)]
 pub(crate) struct SemVer {
-    major: u32,
-    minor: u32,
-    patch: u32,
-    prerelease: String,
-    build: String,
+    pub(crate) major: u32,
+    pub(crate) minor: u32,
+    pub(crate) patch: u32,
+    pub(crate) prerelease: Vec<Identifier>,
+    pub(crate) build: Vec<Identifier>,
+}
+
+// Build metadata is excluded from equality too, so `==` agrees with `Ord`/`cmp`
+// below -- otherwise two versions differing only in build metadata would
+// compare `Equal` yet be unequal.
+impl PartialEq for SemVer {
+    fn eq(&self, other: &Self) -> bool {
+        self.major == other.major
+            && self.minor == other.minor
+            && self.patch == other.patch
+            && self.prerelease == other.prerelease
+    }
+}
+
+impl Eq for SemVer {}
+
+/// A single dot-separated component of a pre-release or build label. A
+/// zero-padded number like `01` is `AlphaNumeric`, not `Numeric`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl From<&str> for Identifier {
+    fn from(value: &str) -> Self {
+        let is_numeric = !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit());
+        let has_leading_zero = value.len() > 1 && value.starts_with('0');
+        if is_numeric && !has_leading_zero {
+            // semver.org places no length limit on numeric identifiers, so a
+            // syntactically numeric value can still overflow u64.
+            value
+                .parse()
+                .map(Identifier::Numeric)
+                .unwrap_or_else(|_| Identifier::AlphaNumeric(value.to_string()))
+        } else {
+            Identifier::AlphaNumeric(value.to_string())
+        }
+    }
+}
+
+impl Ord for Identifier {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Identifier::Numeric(ours), Identifier::Numeric(theirs)) => ours.cmp(theirs),
+            // A purely numeric identifier always has lower precedence than an alphanumeric one.
+            (Identifier::Numeric(_), Identifier::AlphaNumeric(_)) => Ordering::Less,
+            (Identifier::AlphaNumeric(_), Identifier::Numeric(_)) => Ordering::Greater,
+            (Identifier::AlphaNumeric(ours), Identifier::AlphaNumeric(theirs)) => ours.cmp(theirs),
+        }
+    }
+}
+
+impl PartialOrd for Identifier {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(value) => write!(f, "{}", value),
+            Identifier::AlphaNumeric(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+fn parse_identifiers(raw: &str) -> Vec<Identifier> {
+    raw.split('.').map(Identifier::from).collect()
+}
+
+fn join_identifiers(identifiers: &[Identifier]) -> String {
+    identifiers
+        .iter()
+        .map(Identifier::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+impl fmt::Display for SemVer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.prerelease.is_empty() {
+            write!(f, "-{}", join_identifiers(&self.prerelease))?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", join_identifiers(&self.build))?;
+        }
+        Ok(())
+    }
+}
+
+impl serde::Serialize for SemVer {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl PartialOrd for SemVer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SemVer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major
+            .cmp(&other.major)
+            .then_with(|| self.minor.cmp(&other.minor))
+            .then_with(|| self.patch.cmp(&other.patch))
+            .then_with(|| compare_prerelease(&self.prerelease, &other.prerelease))
+    }
+}
+
+// Build metadata is explicitly excluded from precedence per the semver.org spec.
+pub(crate) fn compare_prerelease(ours: &[Identifier], theirs: &[Identifier]) -> Ordering {
+    match (ours.is_empty(), theirs.is_empty()) {
+        (true, true) => Ordering::Equal,
+        // A version without a pre-release has higher precedence than one with.
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => {
+            for (a, b) in ours.iter().zip(theirs.iter()) {
+                let ord = a.cmp(b);
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            // All preceding identifiers are equal; more identifiers wins.
+            ours.len().cmp(&theirs.len())
+        }
+    }
 }
 
 #[cfg(test)]
@@ -94,8 +246,8 @@ mod test {
                 major: 12,
                 minor: 13,
                 patch: 14,
-                prerelease: String::from("pre123"),
-                build: String::from("a")
+                prerelease: vec![super::Identifier::AlphaNumeric(String::from("pre123"))],
+                build: vec![super::Identifier::AlphaNumeric(String::from("a"))]
             }
         );
         assert_eq!(
@@ -104,8 +256,8 @@ mod test {
                 major: 1,
                 minor: 0,
                 patch: 0,
-                prerelease: String::from("alpha"),
-                build: String::from("001")
+                prerelease: vec![super::Identifier::AlphaNumeric(String::from("alpha"))],
+                build: vec![super::Identifier::AlphaNumeric(String::from("001"))]
             }
         );
         assert_eq!(
@@ -114,8 +266,10 @@ mod test {
                 major: 1,
                 minor: 0,
                 patch: 0,
-                prerelease: String::from(""),
-                build: String::from("21AF26D3----117B344092BD")
+                prerelease: Vec::new(),
+                build: vec![super::Identifier::AlphaNumeric(String::from(
+                    "21AF26D3----117B344092BD"
+                ))]
             }
         );
     }
@@ -128,8 +282,8 @@ mod test {
                 major: 12,
                 minor: 13,
                 patch: 0,
-                prerelease: String::from("pre123"),
-                build: String::from("a")
+                prerelease: vec![super::Identifier::AlphaNumeric(String::from("pre123"))],
+                build: vec![super::Identifier::AlphaNumeric(String::from("a"))]
             }
         );
         assert_eq!(
@@ -138,8 +292,8 @@ mod test {
                 major: 12,
                 minor: 13,
                 patch: 0,
-                prerelease: String::from(""),
-                build: String::from("a")
+                prerelease: Vec::new(),
+                build: vec![super::Identifier::AlphaNumeric(String::from("a"))]
             }
         );
         assert_eq!(
@@ -148,8 +302,8 @@ mod test {
                 major: 12,
                 minor: 13,
                 patch: 0,
-                prerelease: String::from(""),
-                build: String::from("")
+                prerelease: Vec::new(),
+                build: Vec::new()
             }
         );
     }
@@ -162,8 +316,8 @@ mod test {
                 major: 12,
                 minor: 0,
                 patch: 0,
-                prerelease: String::from("pre123"),
-                build: String::from("a")
+                prerelease: vec![super::Identifier::AlphaNumeric(String::from("pre123"))],
+                build: vec![super::Identifier::AlphaNumeric(String::from("a"))]
             }
         );
         assert_eq!(
@@ -172,8 +326,8 @@ mod test {
                 major: 12,
                 minor: 0,
                 patch: 0,
-                prerelease: String::from(""),
-                build: String::from("a")
+                prerelease: Vec::new(),
+                build: vec![super::Identifier::AlphaNumeric(String::from("a"))]
             }
         );
         assert_eq!(
@@ -182,9 +336,87 @@ mod test {
                 major: 12,
                 minor: 0,
                 patch: 0,
-                prerelease: String::from(""),
-                build: String::from("")
+                prerelease: Vec::new(),
+                build: Vec::new()
             }
         );
     }
+
+    #[test]
+    fn test_ordering_numeric() {
+        assert!(parse("1.2.3") < parse("1.2.4"));
+        assert!(parse("1.2.3") < parse("1.3.0"));
+        assert!(parse("1.2.3") < parse("2.0.0"));
+        assert_eq!(parse("1.2.3"), parse("1.2.3"));
+    }
+
+    #[test]
+    fn test_ordering_prerelease_has_lower_precedence() {
+        assert!(parse("1.0.0-alpha") < parse("1.0.0"));
+    }
+
+    #[test]
+    fn test_ordering_prerelease_identifiers() {
+        assert!(parse("1.0.0-alpha") < parse("1.0.0-alpha.1"));
+        assert!(parse("1.0.0-alpha.1") < parse("1.0.0-alpha.beta"));
+        assert!(parse("1.0.0-alpha.beta") < parse("1.0.0-beta"));
+        assert!(parse("1.0.0-beta") < parse("1.0.0-beta.2"));
+        assert!(parse("1.0.0-beta.2") < parse("1.0.0-beta.11"));
+        assert!(parse("1.0.0-beta.11") < parse("1.0.0-rc.1"));
+        assert!(parse("1.0.0-rc.1") < parse("1.0.0"));
+    }
+
+    #[test]
+    fn test_ordering_ignores_build_metadata() {
+        assert_eq!(
+            parse("1.0.0+build1").cmp(&parse("1.0.0+build2")),
+            std::cmp::Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_eq_ignores_build_metadata() {
+        assert_eq!(parse("1.0.0+build1"), parse("1.0.0+build2"));
+    }
+
+    #[test]
+    fn test_identifier_overflow_falls_back_to_alphanumeric() {
+        assert_eq!(
+            parse("1.0.0-99999999999999999999").prerelease,
+            vec![super::Identifier::AlphaNumeric(String::from(
+                "99999999999999999999"
+            ))]
+        );
+    }
+
+    #[test]
+    fn test_identifier_leading_zero_is_alphanumeric() {
+        assert_eq!(
+            parse("1.0.0-01").prerelease,
+            vec![super::Identifier::AlphaNumeric(String::from("01"))]
+        );
+        assert_eq!(
+            parse("1.0.0-0").prerelease,
+            vec![super::Identifier::Numeric(0)]
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        assert_eq!(parse("1.2.3").to_string(), "1.2.3");
+        assert_eq!(parse("1.2.3-alpha.1").to_string(), "1.2.3-alpha.1");
+        assert_eq!(parse("1.2.3+build.5").to_string(), "1.2.3+build.5");
+        assert_eq!(
+            parse("1.2.3-alpha.1+build.5").to_string(),
+            "1.2.3-alpha.1+build.5"
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(
+            serde_json::to_string(&parse("1.2.3-alpha+build")).unwrap(),
+            "\"1.2.3-alpha+build\""
+        );
+    }
 }
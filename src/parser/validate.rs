@@ -0,0 +1,47 @@
+use std::cell::RefCell;
+
+/// A single constraint violation, keyed by the field path it occurred at
+/// (e.g. `orders[2].quantity`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub path: String,
+    pub message: String,
+}
+
+/// Accumulates every violation found during a validation pass.
+#[derive(Debug, Default)]
+pub struct ErrorCollector {
+    errors: RefCell<Vec<ValidationError>>,
+}
+
+impl ErrorCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&self, path: impl Into<String>, message: impl Into<String>) {
+        self.errors.borrow_mut().push(ValidationError {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.borrow().is_empty()
+    }
+
+    pub fn into_errors(self) -> Vec<ValidationError> {
+        self.errors.into_inner()
+    }
+}
+
+/// Implemented by each property type to validate and normalize a JSON value
+/// against its metamodel declaration.
+pub trait Validator {
+    /// Validates `value`, pushing every violation onto `errs`, and returns a
+    /// normalized value with defaults filled in.
+    fn validate(&self, value: &serde_json::Value, errs: &ErrorCollector) -> serde_json::Value;
+
+    /// The value to use when the field is absent and optional.
+    fn default(&self) -> Option<serde_json::Value>;
+}
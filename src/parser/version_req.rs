@@ -0,0 +1,313 @@
+use crate::parser::semver::{compare_prerelease, Identifier, SemVer};
+use std::cmp::Ordering;
+
+/// A parsed version-requirement expression, e.g. `^1.2.3` or `>=1.0.0, <2.0.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// A version satisfies the requirement only if it satisfies every
+    /// comma-separated comparator.
+    pub(crate) fn matches(&self, version: &SemVer) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Comparator {
+    op: Op,
+    major: u32,
+    minor: u32,
+    patch: u32,
+    prerelease: Vec<Identifier>,
+}
+
+impl Comparator {
+    fn matches(&self, version: &SemVer) -> bool {
+        if !version.prerelease.is_empty() {
+            // A pre-release only satisfies a comparator that names a
+            // pre-release for that exact major.minor.patch.
+            let same_triplet = self.major == version.major
+                && self.minor == version.minor
+                && self.patch == version.patch;
+            if !same_triplet || self.prerelease.is_empty() {
+                return false;
+            }
+        }
+
+        let ord = (version.major, version.minor, version.patch)
+            .cmp(&(self.major, self.minor, self.patch))
+            .then_with(|| compare_prerelease(&version.prerelease, &self.prerelease));
+        match self.op {
+            Op::Exact => ord == Ordering::Equal,
+            Op::Greater => ord == Ordering::Greater,
+            Op::GreaterEq => ord != Ordering::Less,
+            Op::Less => ord == Ordering::Less,
+            Op::LessEq => ord != Ordering::Greater,
+        }
+    }
+}
+
+/// Parses a version-requirement expression into a [`VersionReq`].
+pub(crate) fn version_req(input: &str) -> Result<VersionReq, Box<dyn std::error::Error>> {
+    let comparators = input
+        .split(',')
+        .map(str::trim)
+        .map(parse_comparators)
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    Ok(VersionReq { comparators })
+}
+
+fn parse_comparators(input: &str) -> Result<Vec<Comparator>, Box<dyn std::error::Error>> {
+    if let Some(rest) = input.strip_prefix(">=") {
+        return Ok(vec![exact_comparator(Op::GreaterEq, rest)?]);
+    }
+    if let Some(rest) = input.strip_prefix("<=") {
+        return Ok(vec![exact_comparator(Op::LessEq, rest)?]);
+    }
+    if let Some(rest) = input.strip_prefix('>') {
+        return Ok(vec![exact_comparator(Op::Greater, rest)?]);
+    }
+    if let Some(rest) = input.strip_prefix('<') {
+        return Ok(vec![exact_comparator(Op::Less, rest)?]);
+    }
+    if let Some(rest) = input.strip_prefix('=') {
+        return Ok(vec![exact_comparator(Op::Exact, rest)?]);
+    }
+    if let Some(rest) = input.strip_prefix('^') {
+        return caret_comparators(rest);
+    }
+    if let Some(rest) = input.strip_prefix('~') {
+        return tilde_comparators(rest);
+    }
+    if input.contains('*') {
+        return wildcard_comparators(input);
+    }
+    Ok(vec![exact_comparator(Op::Exact, input)?])
+}
+
+fn caret_comparators(input: &str) -> Result<Vec<Comparator>, Box<dyn std::error::Error>> {
+    let lower = exact_comparator(Op::GreaterEq, input)?;
+    let (major, minor, patch) = (lower.major, lower.minor, lower.patch);
+    // ^1.2.3 := >=1.2.3, <2.0.0 -- narrowed when a leading component is zero.
+    let upper = if major > 0 {
+        (major + 1, 0, 0)
+    } else if minor > 0 {
+        (0, minor + 1, 0)
+    } else {
+        (0, 0, patch + 1)
+    };
+    Ok(vec![lower, upper_bound_comparator(upper)])
+}
+
+fn tilde_comparators(input: &str) -> Result<Vec<Comparator>, Box<dyn std::error::Error>> {
+    let lower = exact_comparator(Op::GreaterEq, input)?;
+    // ~1.2.3 := >=1.2.3, <1.3.0
+    let upper = (lower.major, lower.minor + 1, 0);
+    Ok(vec![lower, upper_bound_comparator(upper)])
+}
+
+fn wildcard_comparators(input: &str) -> Result<Vec<Comparator>, Box<dyn std::error::Error>> {
+    let parts: Vec<&str> = input.split('.').collect();
+    let major: u32 = parts
+        .first()
+        .filter(|p| **p != "*")
+        .ok_or("wildcard version requirement must specify a major version")?
+        .parse()?;
+    match parts.get(1) {
+        // 1.* := >=1.0.0, <2.0.0
+        None | Some(&"*") => Ok(vec![
+            exact_comparator(Op::GreaterEq, &format!("{}.0.0", major))?,
+            upper_bound_comparator((major + 1, 0, 0)),
+        ]),
+        // 1.2.* := >=1.2.0, <1.3.0
+        Some(minor) => {
+            let minor: u32 = minor.parse()?;
+            Ok(vec![
+                exact_comparator(Op::GreaterEq, &format!("{}.{}.0", major, minor))?,
+                upper_bound_comparator((major, minor + 1, 0)),
+            ])
+        }
+    }
+}
+
+fn upper_bound_comparator((major, minor, patch): (u32, u32, u32)) -> Comparator {
+    Comparator {
+        op: Op::Less,
+        major,
+        minor,
+        patch,
+        prerelease: Vec::new(),
+    }
+}
+
+fn exact_comparator(op: Op, input: &str) -> Result<Comparator, Box<dyn std::error::Error>> {
+    let (version, prerelease) = match input.split_once('-') {
+        Some((version, prerelease)) => (version, parse_identifiers(prerelease)),
+        None => (input, Vec::new()),
+    };
+    let mut parts = version.split('.');
+    let major = parts.next().ok_or("missing major version")?.parse()?;
+    let minor = match parts.next() {
+        Some(m) => m.parse()?,
+        None => 0,
+    };
+    let patch = match parts.next() {
+        Some(p) => p.parse()?,
+        None => 0,
+    };
+    Ok(Comparator {
+        op,
+        major,
+        minor,
+        patch,
+        prerelease,
+    })
+}
+
+fn parse_identifiers(raw: &str) -> Vec<Identifier> {
+    raw.split('.').map(Identifier::from).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::version_req;
+    use crate::parser::semver::SemVer;
+
+    fn semver(major: u32, minor: u32, patch: u32) -> SemVer {
+        SemVer {
+            major,
+            minor,
+            patch,
+            prerelease: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_exact() {
+        let req = version_req("=1.2.3").unwrap();
+        assert!(req.matches(&semver(1, 2, 3)));
+        assert!(!req.matches(&semver(1, 2, 4)));
+    }
+
+    #[test]
+    fn test_caret_full() {
+        let req = version_req("^1.2.3").unwrap();
+        assert!(req.matches(&semver(1, 2, 3)));
+        assert!(req.matches(&semver(1, 9, 0)));
+        assert!(!req.matches(&semver(1, 2, 2)));
+        assert!(!req.matches(&semver(2, 0, 0)));
+    }
+
+    #[test]
+    fn test_caret_zero_major() {
+        let req = version_req("^0.2.3").unwrap();
+        assert!(req.matches(&semver(0, 2, 3)));
+        assert!(req.matches(&semver(0, 2, 9)));
+        assert!(!req.matches(&semver(0, 3, 0)));
+    }
+
+    #[test]
+    fn test_caret_zero_major_minor() {
+        let req = version_req("^0.0.3").unwrap();
+        assert!(req.matches(&semver(0, 0, 3)));
+        assert!(!req.matches(&semver(0, 0, 4)));
+    }
+
+    #[test]
+    fn test_tilde() {
+        let req = version_req("~1.2.3").unwrap();
+        assert!(req.matches(&semver(1, 2, 3)));
+        assert!(req.matches(&semver(1, 2, 9)));
+        assert!(!req.matches(&semver(1, 3, 0)));
+    }
+
+    #[test]
+    fn test_wildcard() {
+        let req = version_req("1.2.*").unwrap();
+        assert!(req.matches(&semver(1, 2, 0)));
+        assert!(req.matches(&semver(1, 2, 99)));
+        assert!(!req.matches(&semver(1, 3, 0)));
+
+        let req = version_req("1.*").unwrap();
+        assert!(req.matches(&semver(1, 0, 0)));
+        assert!(req.matches(&semver(1, 9, 9)));
+        assert!(!req.matches(&semver(2, 0, 0)));
+    }
+
+    #[test]
+    fn test_conjunction() {
+        let req = version_req(">=1.0.0, <2.0.0").unwrap();
+        assert!(req.matches(&semver(1, 5, 0)));
+        assert!(!req.matches(&semver(2, 0, 0)));
+        assert!(!req.matches(&semver(0, 9, 0)));
+    }
+
+    #[test]
+    fn test_prerelease_only_matches_same_triplet_prerelease_comparator() {
+        let req = version_req(">=1.0.0-alpha").unwrap();
+        let prerelease = SemVer {
+            major: 1,
+            minor: 0,
+            patch: 0,
+            prerelease: vec![crate::parser::semver::Identifier::AlphaNumeric(
+                String::from("alpha"),
+            )],
+            build: Vec::new(),
+        };
+        assert!(req.matches(&prerelease));
+
+        let other_prerelease = SemVer {
+            major: 1,
+            minor: 1,
+            patch: 0,
+            prerelease: vec![crate::parser::semver::Identifier::AlphaNumeric(
+                String::from("alpha"),
+            )],
+            build: Vec::new(),
+        };
+        assert!(!req.matches(&other_prerelease));
+    }
+
+    fn prerelease(major: u32, minor: u32, patch: u32, label: &str) -> SemVer {
+        SemVer {
+            major,
+            minor,
+            patch,
+            prerelease: vec![crate::parser::semver::Identifier::AlphaNumeric(
+                String::from(label),
+            )],
+            build: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_exact_compares_prerelease_identifiers() {
+        let req = version_req("=1.2.3-alpha").unwrap();
+        assert!(req.matches(&prerelease(1, 2, 3, "alpha")));
+        assert!(!req.matches(&prerelease(1, 2, 3, "rc")));
+    }
+
+    #[test]
+    fn test_greater_eq_compares_prerelease_identifiers() {
+        let req = version_req(">=1.0.0-beta").unwrap();
+        assert!(req.matches(&prerelease(1, 0, 0, "beta")));
+        assert!(req.matches(&prerelease(1, 0, 0, "rc")));
+        assert!(!req.matches(&prerelease(1, 0, 0, "alpha")));
+    }
+}